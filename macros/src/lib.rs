@@ -4,7 +4,7 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::parse_macro_input;
+use syn::{parse_macro_input, Data, Fields, Lit};
 
 /// Default implementation for `wupf::StaticHandler`.
 #[proc_macro_derive(PluginHandler)]
@@ -23,3 +23,215 @@ pub fn derive_static_handler(input: TokenStream) -> TokenStream {
     }
     .into()
 }
+
+/// Per-field metadata parsed out of a `#[config(...)]` attribute.
+struct FieldConfig {
+    label: String,
+    values: Vec<String>,
+    default: Option<String>,
+}
+
+fn parse_field_config(field: &syn::Field) -> FieldConfig {
+    let name = field.ident.as_ref().unwrap().to_string();
+
+    let mut config = FieldConfig {
+        label: name,
+        values: Vec::new(),
+        default: None,
+    };
+
+    for attr in &field.attrs {
+        if !attr.path().is_ident("config") {
+            continue;
+        }
+
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("label") {
+                let value: Lit = meta.value()?.parse()?;
+                if let Lit::Str(s) = value {
+                    config.label = s.value();
+                }
+            } else if meta.path.is_ident("default") {
+                let value: Lit = meta.value()?.parse()?;
+                if let Lit::Str(s) = value {
+                    config.default = Some(s.value());
+                }
+            } else if meta.path.is_ident("values") {
+                let content;
+                syn::bracketed!(content in meta.input);
+                let list =
+                    content.parse_terminated(<Lit as syn::parse::Parse>::parse, syn::Token![,])?;
+                for value in list {
+                    if let Lit::Str(s) = value {
+                        config.values.push(s.value());
+                    }
+                }
+            }
+
+            Ok(())
+        });
+    }
+
+    config
+}
+
+/// Implements `wupf::config::Config` for a struct whose fields each map to a
+/// WUPS storage item keyed by the field name.
+///
+/// ```ignore
+/// #[derive(Config, Clone, PartialEq)]
+/// struct Settings {
+///     #[config(label = "Speed", values = ["1x", "2x", "4x"], default = "1x")]
+///     speed: Speed,
+/// }
+/// ```
+#[proc_macro_derive(Config, attributes(config))]
+pub fn derive_config(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+
+    let name = &input.ident;
+
+    let Data::Struct(data) = &input.data else {
+        return syn::Error::new_spanned(&input, "Config can only be derived for structs")
+            .to_compile_error()
+            .into();
+    };
+
+    let Fields::Named(fields) = &data.fields else {
+        return syn::Error::new_spanned(&input, "Config requires named fields")
+            .to_compile_error()
+            .into();
+    };
+
+    let mut items = Vec::new();
+    let mut loads = Vec::new();
+    let mut saves = Vec::new();
+    let mut set_by_index_arms = Vec::new();
+
+    for (index, field) in fields.named.iter().enumerate() {
+        let field_name = field.ident.as_ref().unwrap();
+        let key = field_name.to_string();
+        let config = parse_field_config(field);
+
+        let label = &config.label;
+        let values = &config.values;
+        let default = config.default.clone().unwrap_or_default();
+
+        items.push(quote! {
+            ::wupf::config::ConfigItem {
+                key: #key,
+                label: #label,
+                values: &[#(#values),*],
+                default: #default,
+            }
+        });
+
+        // The declared `default = "..."` is the fallback for a missing or
+        // type-mismatched storage key; `T::default()` only backstops a
+        // default string that doesn't parse (or wasn't given one at all).
+        loads.push(quote! {
+            #field_name: ::wupf::config::load_item(#key, {
+                const DEFAULT: &str = #default;
+                DEFAULT.parse().unwrap_or_default()
+            })
+        });
+
+        saves.push(quote! {
+            ::wupf::config::store_item(#key, &self.#field_name, &previous.#field_name)
+        });
+
+        set_by_index_arms.push(quote! {
+            #index => {
+                if let Ok(value) = value.parse() {
+                    self.#field_name = value;
+                }
+            }
+        });
+    }
+
+    quote! {
+        impl ::wupf::config::Config for #name {
+            const ITEMS: &'static [::wupf::config::ConfigItem] = &[#(#items),*];
+
+            fn load() -> Self {
+                Self {
+                    #(#loads),*
+                }
+            }
+
+            fn save(&self, previous: &Self) {
+                #(#saves;)*
+            }
+
+            fn set_by_index(&mut self, index: usize, value: &str) {
+                match index {
+                    #(#set_by_index_arms)*
+                    _ => {}
+                }
+            }
+        }
+    }
+    .into()
+}
+
+/// Exposes a function as a native export other WUPS plugins can resolve by
+/// name, at runtime, through `wupf::import!`.
+///
+/// The original function is left untouched and usable normally. This adds
+/// an `extern "C"` trampoline, with every argument and the return value
+/// routed through `wupf::Marshal` so non-FFI-safe types like `&str` cross
+/// the boundary correctly, and registers it under a stable, namespaced
+/// symbol via WUPS's export mechanism so other plugins can look it up.
+///
+/// ```ignore
+/// #[wupf::export]
+/// fn add(a: i32, b: i32) -> i32 {
+///     a + b
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn export(_args: TokenStream, input: TokenStream) -> TokenStream {
+    let func = parse_macro_input!(input as syn::ItemFn);
+
+    let name = &func.sig.ident;
+    let symbol_ident = quote::format_ident!("__wupf_export_{}", name);
+    let symbol = symbol_ident.to_string();
+
+    let mut raw_params = Vec::new();
+    let mut call_args = Vec::new();
+
+    for arg in &func.sig.inputs {
+        let syn::FnArg::Typed(pat) = arg else {
+            continue;
+        };
+        let pat_ident = &pat.pat;
+        let ty = &pat.ty;
+
+        raw_params.push(quote! { #pat_ident: <#ty as ::wupf::Marshal>::Raw });
+        call_args
+            .push(quote! { unsafe { <#ty as ::wupf::Marshal>::from_raw(#pat_ident) } });
+    }
+
+    let trampoline = match &func.sig.output {
+        syn::ReturnType::Default => quote! {
+            extern "C" fn #symbol_ident(#(#raw_params),*) {
+                #name(#(#call_args),*);
+            }
+        },
+        syn::ReturnType::Type(_, ty) => quote! {
+            extern "C" fn #symbol_ident(#(#raw_params),*) -> <#ty as ::wupf::Marshal>::Raw {
+                ::wupf::Marshal::into_raw(#name(#(#call_args),*))
+            }
+        },
+    };
+
+    quote! {
+        #func
+
+        #[no_mangle]
+        #trampoline
+
+        ::wups::wups_export_ex!(#symbol, #symbol_ident);
+    }
+    .into()
+}
@@ -0,0 +1,190 @@
+//! Persistent, typed plugin configuration backed by WUPS storage.
+//!
+//! This mirrors libretro's core-option model: each field of a `#[derive(Config)]`
+//! struct becomes a named storage item with an optional human-readable label and,
+//! for enum-like fields, a list of allowed values plus a default.
+
+use wups::storage;
+
+/// Metadata describing a single configurable field.
+///
+/// Generated by the [`Config`][macro@crate::Config] derive macro, one entry per
+/// field (annotated or not), in declaration order.
+pub struct ConfigItem {
+    /// Storage key, identical to the field name.
+    pub key: &'static str,
+    /// Human-readable label shown in the WUPS config menu.
+    pub label: &'static str,
+    /// Allowed values for enum/bounded fields, empty otherwise.
+    pub values: &'static [&'static str],
+    /// Default value, used both as the storage fallback and the initial
+    /// selection in the config menu.
+    pub default: &'static str,
+}
+
+/// Typed plugin settings persisted in WUPS storage.
+///
+/// Implemented by the [`Config`][macro@crate::Config] derive macro, which maps
+/// each field to a storage item keyed by the field's name. Every field must
+/// implement `FromStr` and `Default`: `FromStr` to parse both the declared
+/// `default = "..."` string and edits made in the config menu, `Default` as
+/// the last-resort fallback when a default string is missing or doesn't
+/// parse.
+pub trait Config: Sized + Clone + PartialEq {
+    /// Metadata for every configurable field, in declaration order. Index
+    /// `i` here corresponds to the `i` passed to
+    /// [`Config::set_by_index`].
+    const ITEMS: &'static [ConfigItem];
+
+    /// Load every field from storage.
+    ///
+    /// Falls back to the field's declared default whenever the key is
+    /// missing or the stored value doesn't have the expected type.
+    fn load() -> Self;
+
+    /// Write every field to storage.
+    ///
+    /// Only keys whose value actually changed are written, to avoid
+    /// needless flash wear.
+    fn save(&self, previous: &Self);
+
+    /// Parse `value` and assign it to the field at `index` into
+    /// [`Config::ITEMS`]. Used to apply a single edit made in the WUPS
+    /// config menu without reloading every other field. Does nothing if
+    /// `value` doesn't parse as that field's type.
+    fn set_by_index(&mut self, index: usize, value: &str);
+}
+
+/// Read a single storage item, falling back to `default` on a missing key
+/// or a type mismatch.
+///
+/// Used by the generated [`Config::load`] implementation; plugins normally
+/// don't call this directly.
+pub fn load_item<T>(key: &str, default: T) -> T
+where
+    T: storage::Item,
+{
+    storage::load(key).unwrap_or(default)
+}
+
+/// Write a single storage item if it differs from `previous`.
+///
+/// Used by the generated [`Config::save`] implementation; plugins normally
+/// don't call this directly.
+pub fn store_item<T>(key: &str, value: &T, previous: &T)
+where
+    T: storage::Item + PartialEq,
+{
+    if value != previous {
+        let _ = storage::store(key, value);
+    }
+}
+
+/// Register one [`ConfigItem`] with the WUPS config menu.
+///
+/// Translates our own [`ConfigItem`] into primitive `label`/`values`/
+/// `default` arguments instead of handing WUPS a `wupf`-defined type it has
+/// no knowledge of; used by [`OnConfigMenu::ffi_on_config_open`].
+///
+/// # Caveat
+///
+/// The real WUPS config API is a family of typed constructors (boolean,
+/// multiple-choice, integer range, ...), not one generic "add item"
+/// function, and the exact argument list for each hasn't been confirmed
+/// against the real `wups` crate. This always goes through the
+/// multiple-choice shape, which fits [`ConfigItem::values`] but should be
+/// checked (and this adapter adjusted per item kind if needed) before
+/// relying on it.
+fn register_item(
+    root: wups::config::CategoryHandle,
+    item: &ConfigItem,
+    index: u32,
+    on_change: extern "C" fn(u32, *const core::ffi::c_char),
+) {
+    wups::config::add_item(root, item.label, item.values, item.default, index, on_change);
+}
+
+/// Surfaces a [`Config`] in the WUPS config menu and reacts to edits made there.
+///
+/// Implement this on the same type that implements [`Plugin`][crate::Plugin];
+/// [`Self::Settings`] is the `#[derive(Config)]` struct embedded in the
+/// handler's state.
+pub trait OnConfigMenu: crate::Plugin {
+    /// The persisted settings this plugin exposes in the config menu.
+    type Settings: Config;
+
+    /// Mutable access to the live settings embedded in the handler's state.
+    fn settings_mut(&mut self) -> &mut Self::Settings;
+
+    /// Called after a config menu edit has been applied and persisted, and
+    /// once more when the config menu is closed.
+    fn on_config_changed(&mut self, settings: &Self::Settings) {
+        let _ = settings;
+    }
+
+    /// FFI callback for [`Plugin::ffi_on_init`][crate::Plugin::ffi_on_init],
+    /// loading [`Self::Settings`] into the handler state built by
+    /// [`Plugin::on_init`][crate::Plugin::on_init] before anything else runs.
+    ///
+    /// Wired in by `hook_plugin!($plugin, config)` in place of the stock
+    /// `Plugin::ffi_on_init`, so persisted settings are in place before
+    /// `on_start` can see them, without every plugin having to remember to
+    /// call `Settings::load()` itself.
+    ///
+    /// **Do not overwrite** this method unless you need to and know what you
+    /// are doing!
+    extern "C" fn ffi_on_init() {
+        let handler = Self::handler();
+
+        let mut app = Self::on_init();
+        *app.settings_mut() = Self::Settings::load();
+        handler.set(app);
+    }
+
+    /// FFI callback building the WUPS config menu from
+    /// `Self::Settings::ITEMS`.
+    ///
+    /// **Do not overwrite** this method unless you need to and know what you
+    /// are doing!
+    extern "C" fn ffi_on_config_open(root: wups::config::CategoryHandle) {
+        for (index, item) in Self::Settings::ITEMS.iter().enumerate() {
+            register_item(root, item, index as u32, Self::ffi_on_item_changed);
+        }
+    }
+
+    /// FFI callback fired by WUPS when a single item is edited in the menu.
+    ///
+    /// Applies the edit to the live settings, persists only the changed
+    /// key, and forwards the up-to-date settings to
+    /// [`OnConfigMenu::on_config_changed`].
+    ///
+    /// **Do not overwrite** this method unless you need to and know what you
+    /// are doing!
+    extern "C" fn ffi_on_item_changed(index: u32, value: *const core::ffi::c_char) {
+        let Ok(value) = (unsafe { core::ffi::CStr::from_ptr(value) }).to_str() else {
+            return;
+        };
+
+        let handler = Self::handler().get();
+        let mut app = handler.lock().unwrap();
+
+        let previous = app.settings_mut().clone();
+        app.settings_mut().set_by_index(index as usize, value);
+
+        let settings = app.settings_mut().clone();
+        settings.save(&previous);
+        app.on_config_changed(&settings);
+    }
+
+    /// FFI callback invoked when the config menu is closed.
+    ///
+    /// **Do not overwrite** this method unless you need to and know what you
+    /// are doing!
+    extern "C" fn ffi_on_config_closed() {
+        let handler = Self::handler().get();
+        let mut app = handler.lock().unwrap();
+
+        let settings = app.settings_mut().clone();
+        app.on_config_changed(&settings);
+    }
+}
@@ -0,0 +1,91 @@
+//! Exported inter-plugin native functions.
+//!
+//! Mirrors SourceMod-style natives: a plugin exposes functions to other WUPS
+//! plugins with [`wupf::export`][macro@crate::export], and imports another
+//! plugin's export by name, at runtime, with [`import!`].
+
+/// A scalar or string value that can cross the plugin export/import
+/// boundary without the caller hand-writing FFI conversions.
+pub trait Marshal: Sized {
+    /// The `extern "C"`-safe representation used on the wire.
+    type Raw: Copy;
+
+    /// Convert into the wire representation.
+    fn into_raw(self) -> Self::Raw;
+
+    /// Reconstruct from the wire representation.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must have been produced by [`Marshal::into_raw`] for the same
+    /// `Self`, and any borrowed data it points to must still be alive.
+    unsafe fn from_raw(raw: Self::Raw) -> Self;
+}
+
+macro_rules! impl_marshal_scalar {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Marshal for $ty {
+                type Raw = $ty;
+
+                fn into_raw(self) -> Self::Raw {
+                    self
+                }
+
+                unsafe fn from_raw(raw: Self::Raw) -> Self {
+                    raw
+                }
+            }
+        )*
+    };
+}
+
+impl_marshal_scalar!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64, bool);
+
+/// `(pointer, length)` pair for a UTF-8 string crossing the export/import
+/// boundary.
+///
+/// `#[repr(C)]`, unlike a bare Rust tuple, so it has a defined layout in
+/// `extern "C"` parameter and return positions.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct RawStr {
+    pub ptr: *const u8,
+    pub len: usize,
+}
+
+/// A UTF-8 string, passed across the boundary as a [`RawStr`].
+impl Marshal for &str {
+    type Raw = RawStr;
+
+    fn into_raw(self) -> Self::Raw {
+        RawStr {
+            ptr: self.as_ptr(),
+            len: self.len(),
+        }
+    }
+
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        core::str::from_utf8_unchecked(core::slice::from_raw_parts(raw.ptr, raw.len))
+    }
+}
+
+/// Resolve another plugin's `#[export]`ed function by its stable symbol
+/// name. Used by [`import!`]; plugins normally don't call this directly.
+///
+/// Returns `None` if no loaded plugin currently exports that symbol.
+///
+/// # Caveat
+///
+/// This assumes `wups::exports::find_symbol` exists as a runtime,
+/// string-keyed lookup, and that `wups_export_ex!` (invoked by
+/// [`export`][macro@crate::export]) registers under that same name. Real
+/// WUPS plugin imports are normally resolved by the loader at load time,
+/// not via an ad hoc runtime lookup; this pairing has not been confirmed
+/// against the actual `wups` crate and should be checked (and this module
+/// adjusted to whatever the real API is) before relying on it — as shipped,
+/// a wrong assumption here means [`import!`] silently returns `None` for
+/// every call instead of failing loudly.
+pub fn resolve(symbol: &str) -> Option<*const ()> {
+    wups::exports::find_symbol(symbol)
+}
@@ -0,0 +1,24 @@
+//! Generalized hook abstraction over arbitrary Cafe OS functions.
+//!
+//! The crate's other hook macros (`hook_on_input!`, `hook_on_update!`,
+//! `hook_plugin!`) are each hardwired to one specific `(module, function)`
+//! pair. [`FunctionHook`] plus the [`hook!`](crate::hook) macro let a
+//! plugin declare a hook over any pair it needs instead, receiving typed
+//! arguments and a trampoline back to the original function.
+
+/// Handles one intercepted call to a hooked Cafe OS function.
+///
+/// Implemented for a plugin's handler type, once per hooked
+/// `(module, function)` pair, by the [`hook!`](crate::hook) macro (and the
+/// convenience hooks built on top of it, like
+/// [`hook_on_filesystem_open!`](crate::hook_on_filesystem_open) and
+/// [`hook_on_app_launch!`](crate::hook_on_app_launch)).
+pub trait FunctionHook<Args, Ret>: crate::Plugin {
+    /// Handle one call.
+    ///
+    /// `call_original` invokes the hooked function on the caller's behalf
+    /// with the given arguments. The implementation can call it zero times
+    /// to skip the original call or once (the common case), and is free to
+    /// rewrite `Ret` before returning it.
+    fn on_call(&mut self, args: Args, call_original: impl FnOnce(Args) -> Ret) -> Ret;
+}
@@ -0,0 +1,182 @@
+//! Button-chord / hotkey detection layered on top of [`OnInput`].
+//!
+//! Plugins previously had to diff raw [`gamepad::State`] themselves across
+//! frames to notice "just pressed" or "held together" transitions. A
+//! [`Hotkeys`] registry does that bookkeeping once, per [`gamepad::Port`],
+//! and dispatches to a callback when a registered chord or sequence fires.
+
+use alloc::vec::Vec;
+
+use wut::gamepad::{Port, State};
+
+/// Per-port button history, used to derive edge transitions.
+struct PortHistory {
+    port: Port,
+    previous: State,
+    /// Frames the current state (as a whole) has been held unchanged.
+    held_frames: u32,
+    /// Buttons pressed so far towards each in-progress [`Sequence`], and how
+    /// many frames are left to complete the next step.
+    progress: Vec<(usize, u32)>,
+}
+
+/// A set of buttons that must all be held down on the same frame.
+pub struct Chord {
+    buttons: State,
+    consume: bool,
+    callback: fn(Port),
+}
+
+/// An ordered list of button presses that must each occur within `window`
+/// frames of the previous one.
+pub struct Sequence {
+    steps: Vec<State>,
+    window: u32,
+    consume: bool,
+    callback: fn(Port),
+}
+
+/// Registry of chords and sequences, tracking per-port input history.
+///
+/// Own one of these in the plugin's handler state, register hotkeys once in
+/// [`Plugin::on_init`][crate::Plugin::on_init], and call [`Hotkeys::update`]
+/// from [`OnInput::on_input`].
+#[derive(Default)]
+pub struct Hotkeys {
+    chords: Vec<Chord>,
+    sequences: Vec<Sequence>,
+    ports: Vec<PortHistory>,
+}
+
+impl Hotkeys {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a chord: `callback` fires on the frame `buttons` becomes
+    /// fully held, provided it wasn't already fully held the frame before
+    /// (so topping it off by pressing the last remaining button counts,
+    /// even if the others were already held). If `consume` is set, the
+    /// triggering buttons are masked out of the state returned to the game.
+    pub fn chord(&mut self, buttons: State, consume: bool, callback: fn(Port)) {
+        self.chords.push(Chord {
+            buttons,
+            consume,
+            callback,
+        });
+    }
+
+    /// Register a sequence: each entry in `steps` must be newly pressed, in
+    /// order, with no more than `window` frames between consecutive steps.
+    pub fn sequence(&mut self, steps: Vec<State>, window: u32, consume: bool, callback: fn(Port)) {
+        self.sequences.push(Sequence {
+            steps,
+            window,
+            consume,
+            callback,
+        });
+    }
+
+    /// Frames `port`'s state has been held unchanged, for plugins that want
+    /// a direct "held for N frames" check instead of registering a chord.
+    pub fn held_frames(&self, port: Port) -> u32 {
+        self.ports
+            .iter()
+            .find(|history| history.port == port)
+            .map_or(0, |history| history.held_frames)
+    }
+
+    fn history(&mut self, port: Port) -> &mut PortHistory {
+        if let Some(index) = self.ports.iter().position(|history| history.port == port) {
+            &mut self.ports[index]
+        } else {
+            self.ports.push(PortHistory {
+                port,
+                previous: State::default(),
+                held_frames: 0,
+                progress: Vec::new(),
+            });
+            self.ports.last_mut().unwrap()
+        }
+    }
+
+    /// Feed this frame's state for `port` through every registered chord and
+    /// sequence, firing callbacks as they complete.
+    ///
+    /// Returns `state` with the buttons of any triggered, consuming hotkey
+    /// masked out; pass the result back as the return value of
+    /// [`OnInput::on_input`].
+    pub fn update(&mut self, port: Port, state: State) -> State {
+        let chords = core::mem::take(&mut self.chords);
+        let sequences = core::mem::take(&mut self.sequences);
+
+        let history = self.history(port);
+        let just_pressed = state & !history.previous;
+
+        if state == history.previous {
+            history.held_frames += 1;
+        } else {
+            history.held_frames = 0;
+        }
+
+        let mut remaining = state;
+
+        for chord in &chords {
+            let fully_held = state & chord.buttons == chord.buttons;
+            let freshly_completed = fully_held && (history.previous & chord.buttons) != chord.buttons;
+
+            if freshly_completed {
+                (chord.callback)(port);
+                if chord.consume {
+                    remaining = remaining & !chord.buttons;
+                }
+            }
+        }
+
+        while history.progress.len() < sequences.len() {
+            history.progress.push((0, 0));
+        }
+
+        for (index, sequence) in sequences.iter().enumerate() {
+            let (step, ticks_left) = history.progress[index];
+
+            if step > 0 {
+                if ticks_left == 0 {
+                    history.progress[index] = (0, 0);
+                    continue;
+                }
+                history.progress[index].1 = ticks_left - 1;
+            }
+
+            let (step, _) = history.progress[index];
+            let Some(&expected) = sequence.steps.get(step) else {
+                continue;
+            };
+
+            if just_pressed & expected == expected {
+                let next = step + 1;
+
+                if next == sequence.steps.len() {
+                    history.progress[index] = (0, 0);
+                    (sequence.callback)(port);
+                    if sequence.consume {
+                        remaining = remaining & !expected;
+                    }
+                } else {
+                    history.progress[index] = (next, sequence.window);
+                    if sequence.consume {
+                        remaining = remaining & !expected;
+                    }
+                }
+            }
+        }
+
+        history.previous = state;
+
+        self.chords = chords;
+        self.sequences = sequences;
+
+        remaining
+    }
+}
@@ -44,8 +44,28 @@
 
 #![no_std]
 
+extern crate alloc;
+
+pub mod config;
+pub mod export;
+pub mod hook;
+pub mod hotkeys;
+#[cfg(feature = "serde")]
+pub mod persist;
+pub mod task;
+
 #[cfg(feature = "derive")]
-pub use macros::PluginHandler;
+pub use macros::{export, Config, PluginHandler};
+
+pub use config::{Config, ConfigItem, OnConfigMenu};
+pub use export::Marshal;
+pub use hook::FunctionHook;
+pub use hotkeys::Hotkeys;
+#[cfg(feature = "serde")]
+pub use persist::{PersistentState, WithPersistentState};
+pub use task::{spawn, Delay, FrameTimer, OnUpdateAsync};
+
+use alloc::vec::Vec;
 
 use wut::{
     self,
@@ -58,6 +78,7 @@ use wut::{
 /// Contains the state of the plugin to allow synced and mutable state.
 pub struct Handler<P> {
     inner: OnceLock<Mutex<P>>,
+    tasks: Mutex<Vec<task::Task>>,
 }
 
 impl<P> Handler<P> {
@@ -65,17 +86,25 @@ impl<P> Handler<P> {
     pub const fn new() -> Self {
         Self {
             inner: OnceLock::new(),
+            tasks: Mutex::new(Vec::new()),
         }
     }
 
-    fn set(&self, p: P) {
+    pub(crate) fn set(&self, p: P) {
         let _ = self.inner.set(Mutex::new(p));
     }
 
-    fn get(&self) -> &Mutex<P> {
+    pub(crate) fn get(&self) -> &Mutex<P> {
         self.inner.get().expect("Handler not initialized")
     }
 
+    fn push_task(&self, task: task::Task) {
+        self.tasks
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner())
+            .push(task);
+    }
+
     // fn take(&self) -> Mutex<P> {
     //     let mut inner = self.inner.borrow_mut();
     //     let value = core::mem::replace(&mut *inner, MaybeUninit::uninit());
@@ -174,6 +203,13 @@ pub trait OnInput: Plugin {
     ///
     /// **Do not overwrite** this method unless you need to and know what you are doing!
     extern "C" fn ffi_on_vpad(
+        // Unlike `ffi_on_kpad`'s `chan`, which selects between Wii Remote /
+        // Pro Controller slots via `Port::from_wpad`, VPAD has no such
+        // per-channel `Port` to select: the GamePad is the only device it
+        // ever reports, so `Port::DRC` isn't a shortcut standing in for a
+        // missing mapping, it's the whole mapping. Closing this out as-is
+        // rather than introducing a `Port::from_vpad` that would have
+        // nothing but `DRC` to return.
         _chan: wut::bindings::VPADChan::Type,
         buffers: *mut wut::bindings::VPADStatus,
         _count: u32,
@@ -237,6 +273,43 @@ macro_rules! hook_plugin {
         ::wups::wups_hook_ex!("APPLICATION_STARTS", $plugin::ffi_on_start);
         ::wups::wups_hook_ex!("APPLICATION_REQUESTS_EXIT", $plugin::ffi_on_exit);
     };
+
+    // Same as the base form, but restoring `Self::Settings` via
+    // `OnConfigMenu::ffi_on_init` instead of `Plugin::ffi_on_init`, plus
+    // wiring up the config menu's open/closed callbacks.
+    ($plugin:ident, config) => {
+        ::wups::wups_hook_ex!(
+            "INIT_PLUGIN",
+            <$plugin as ::wupf::OnConfigMenu>::ffi_on_init
+        );
+        ::wups::wups_hook_ex!("DEINIT_PLUGIN", $plugin::ffi_on_deinit);
+        ::wups::wups_hook_ex!("APPLICATION_STARTS", $plugin::ffi_on_start);
+        ::wups::wups_hook_ex!("APPLICATION_REQUESTS_EXIT", $plugin::ffi_on_exit);
+        ::wups::wups_hook_ex!(
+            "CONFIG_MENU_OPEN",
+            <$plugin as ::wupf::OnConfigMenu>::ffi_on_config_open
+        );
+        ::wups::wups_hook_ex!(
+            "CONFIG_CLOSED",
+            <$plugin as ::wupf::OnConfigMenu>::ffi_on_config_closed
+        );
+    };
+
+    // Same as the base form, but restoring/persisting state via
+    // `WithPersistentState` instead of `Plugin::ffi_on_init`/`ffi_on_deinit`.
+    // Only usable with the `serde` feature enabled.
+    ($plugin:ident, persist) => {
+        ::wups::wups_hook_ex!(
+            "INIT_PLUGIN",
+            <$plugin as ::wupf::WithPersistentState>::ffi_on_init
+        );
+        ::wups::wups_hook_ex!(
+            "DEINIT_PLUGIN",
+            <$plugin as ::wupf::WithPersistentState>::ffi_on_deinit
+        );
+        ::wups::wups_hook_ex!("APPLICATION_STARTS", $plugin::ffi_on_start);
+        ::wups::wups_hook_ex!("APPLICATION_REQUESTS_EXIT", $plugin::ffi_on_exit);
+    };
 }
 
 #[macro_export]
@@ -284,4 +357,146 @@ macro_rules! hook_on_update {
             $plugin::ffi_on_update();
         }
     };
+
+    // Same as above, but for plugins implementing `OnUpdateAsync`: polls
+    // pending tasks before forwarding to `OnUpdate::ffi_on_update`.
+    ($plugin:ident, async) => {
+        #[::wups::function_hook(module = GX2, function = GX2SwapScanBuffers)]
+        fn plugin_GX2SwapScanBuffers() {
+            unsafe {
+                hooked();
+            }
+
+            $plugin::ffi_on_update_async();
+        }
+    };
+}
+
+/// Resolve another WUPS plugin's [`export`]ed function by name.
+///
+/// Expands to `Option<impl Fn(..) -> ..>`, `None` if no loaded plugin
+/// currently exports a function under that name. Arguments and the return
+/// value are routed through [`Marshal`] to match the trampoline
+/// [`export`] generates, so non-FFI-safe types like `&str` work correctly.
+///
+/// See [`export::resolve`]'s doc for a caveat: resolution goes through an
+/// unconfirmed `wups::exports::find_symbol`/`wups_export_ex!` pairing that
+/// needs checking against the real `wups` crate.
+///
+/// ```ignore
+/// if let Some(add) = wupf::import!("add", fn(a: i32, b: i32) -> i32) {
+///     println!("{}", add(1, 2));
+/// }
+/// ```
+#[macro_export]
+macro_rules! import {
+    ($name:literal, fn($($arg:ident : $ty:ty),* $(,)?) -> $ret:ty) => {
+        $crate::export::resolve(::core::concat!("__wupf_export_", $name)).map(
+            |address| {
+                let raw: extern "C" fn($(<$ty as $crate::Marshal>::Raw),*) -> <$ret as $crate::Marshal>::Raw =
+                    unsafe { ::core::mem::transmute(address) };
+
+                move |$($arg: $ty),*| -> $ret {
+                    unsafe {
+                        $crate::Marshal::from_raw(raw($($crate::Marshal::into_raw($arg)),*))
+                    }
+                }
+            },
+        )
+    };
+}
+
+/// Declare a hook over an arbitrary Cafe OS `(module, function)` pair.
+///
+/// Expands to a [`FunctionHook`] implementation for `plugin` plus the
+/// `#[wups::function_hook]` boilerplate wiring it up. The closure is handed
+/// the call's arguments (as a tuple) and `call_original`, a trampoline to
+/// the hooked function; it can call it, skip it, or rewrite its return
+/// value before handing it back.
+///
+/// ```ignore
+/// wupf::hook! {
+///     module = SYSAPP, function = SYSLaunchMenu,
+///     plugin = MyApp,
+///     fn() -> (),
+///     |_args, call_original| {
+///         call_original(())
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! hook {
+    (
+        module = $module:ident,
+        function = $function:ident,
+        plugin = $plugin:ty,
+        fn($($arg:ident : $ty:ty),* $(,)?) -> $ret:ty,
+        |$args:pat_param, $call_original:ident| $body:expr
+    ) => {
+        impl $crate::FunctionHook<($($ty,)*), $ret> for $plugin {
+            fn on_call(
+                &mut self,
+                $args: ($($ty,)*),
+                $call_original: impl FnOnce(($($ty,)*)) -> $ret,
+            ) -> $ret {
+                $body
+            }
+        }
+
+        #[::wups::function_hook(module = $module, function = $function)]
+        fn $function($($arg: $ty),*) -> $ret {
+            let args = ($($arg,)*);
+            let call_original = |args: ($($ty,)*)| {
+                let ($($arg,)*) = args;
+                unsafe { hooked($($arg),*) }
+            };
+
+            let handler = <$plugin as $crate::StaticHandler>::handler().get();
+            let mut app = handler.lock().unwrap();
+
+            $crate::FunctionHook::on_call(&mut *app, args, call_original)
+        }
+    };
+}
+
+/// Hook `FSOpenFile`, called whenever the running application opens a file.
+///
+/// A thin [`hook!`] wrapper to prove the generic hook surface covers more
+/// than input and frame updates; see [`hook!`] for the closure's shape.
+#[macro_export]
+macro_rules! hook_on_filesystem_open {
+    ($plugin:ty, |$args:pat_param, $call_original:ident| $body:expr) => {
+        $crate::hook! {
+            module = FS,
+            function = FSOpenFile,
+            plugin = $plugin,
+            fn(
+                client: *mut ::wut::bindings::FSClient,
+                block: *mut ::wut::bindings::FSCmdBlock,
+                path: *const u8,
+                mode: *const u8,
+                handle: *mut ::wut::bindings::FSFileHandle,
+                error_flag: ::wut::bindings::FSErrorFlag::Type,
+            ) -> i32,
+            |$args, $call_original| $body
+        }
+    };
+}
+
+/// Hook `SYSLaunchMenu`, called whenever the running application launches
+/// the Wii U Menu.
+///
+/// A thin [`hook!`] wrapper to prove the generic hook surface covers more
+/// than input and frame updates; see [`hook!`] for the closure's shape.
+#[macro_export]
+macro_rules! hook_on_app_launch {
+    ($plugin:ty, |$args:pat_param, $call_original:ident| $body:expr) => {
+        $crate::hook! {
+            module = SYSAPP,
+            function = SYSLaunchMenu,
+            plugin = $plugin,
+            fn() -> (),
+            |$args, $call_original| $body
+        }
+    };
 }
@@ -0,0 +1,99 @@
+//! State serialization for survival across plugin reload/unload.
+//!
+//! Plugin state is normally dropped on `on_deinit` and rebuilt from scratch
+//! in `on_init`, so nothing survives an Aroma plugin reload. This mirrors
+//! libretro's `serialize`/`unserialize` savestate flow: a handler's state is
+//! serialized into WUPS storage on `ffi_on_deinit` and restored on
+//! `ffi_on_init` instead.
+
+use alloc::vec::Vec;
+
+use wups::storage;
+
+use crate::{Handler, Plugin, StaticHandler};
+
+/// A handler state that can be serialized into WUPS storage and restored on
+/// the next load.
+///
+/// A small version tag is stored alongside the payload so that changing the
+/// struct's layout invalidates old blobs instead of deserializing garbage
+/// into it; bump [`PersistentState::VERSION`] whenever the layout changes.
+pub trait PersistentState: Sized + serde::Serialize + serde::de::DeserializeOwned {
+    /// Storage key the serialized blob is kept under.
+    const KEY: &'static str;
+
+    /// Bump whenever this type's layout changes.
+    const VERSION: u32;
+
+    /// Built when there is no valid saved state: missing key, corrupt blob,
+    /// or a [`PersistentState::VERSION`] mismatch.
+    fn on_init() -> Self;
+
+    /// Restore from storage, falling back to [`PersistentState::on_init`] if
+    /// nothing usable is there.
+    fn restore() -> Self {
+        storage::load_bytes(Self::KEY)
+            .and_then(Self::decode)
+            .unwrap_or_else(Self::on_init)
+    }
+
+    fn decode(bytes: Vec<u8>) -> Option<Self> {
+        let (version, payload) = bytes.split_first_chunk::<4>()?;
+        if u32::from_le_bytes(*version) != Self::VERSION {
+            return None;
+        }
+
+        postcard::from_bytes(payload).ok()
+    }
+
+    /// Serialize and persist to storage.
+    fn persist(&self) {
+        let Ok(payload) = postcard::to_allocvec(self) else {
+            return;
+        };
+
+        let mut bytes = Vec::with_capacity(4 + payload.len());
+        bytes.extend_from_slice(&Self::VERSION.to_le_bytes());
+        bytes.extend_from_slice(&payload);
+
+        let _ = storage::store_bytes(Self::KEY, &bytes);
+    }
+}
+
+/// A [`Plugin`] whose handler state is restored from and saved back to a
+/// [`PersistentState`], instead of always starting from
+/// [`Plugin::on_init`].
+pub trait WithPersistentState: Plugin {
+    /// The serializable state this plugin's handler is built from.
+    type State: PersistentState;
+
+    /// Build the handler's runtime state from restored (or freshly
+    /// initialized) persistent state.
+    fn from_state(state: Self::State) -> Self;
+
+    /// Extract the persistent state out of the handler's runtime state.
+    fn to_state(&self) -> Self::State;
+
+    /// FFI callback for [`Plugin::ffi_on_init`], restoring persisted state
+    /// instead of always calling [`Plugin::on_init`].
+    ///
+    /// **Do not overwrite** this method unless you need to and know what you
+    /// are doing!
+    extern "C" fn ffi_on_init() {
+        let handler: &Handler<Self> = Self::handler();
+        handler.set(Self::from_state(Self::State::restore()));
+    }
+
+    /// FFI callback for [`Plugin::ffi_on_deinit`], persisting state before
+    /// calling [`Plugin::on_deinit`].
+    ///
+    /// **Do not overwrite** this method unless you need to and know what you
+    /// are doing!
+    extern "C" fn ffi_on_deinit() {
+        let handler = Self::handler().get();
+        let mut app = handler.lock().unwrap();
+
+        app.to_state().persist();
+        app.on_deinit();
+    }
+}
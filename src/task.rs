@@ -0,0 +1,142 @@
+//! Cooperative async executor driven by the [`OnUpdate`] frame hook.
+//!
+//! There's no real reactor: every pending task is polled exactly once per
+//! frame, and the [`Waker`] it's given is a no-op, since a task that isn't
+//! done yet is simply polled again on the next frame regardless of whether
+//! it woke itself. This lets plugins write long-running logic as `async`
+//! functions instead of hand-rolled per-frame state machines.
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    time::Duration,
+};
+
+use alloc::boxed::Box;
+
+use crate::{OnUpdate, StaticHandler};
+
+pub(crate) type Task = Pin<Box<dyn Future<Output = ()>>>;
+
+/// Queue a future to run on `P`'s frame executor.
+///
+/// The future is polled once per frame, from
+/// [`OnUpdateAsync::ffi_on_update_async`], until it completes. Safe to call before
+/// `P`'s handler is initialized: the task is simply held in the queue until
+/// the first poll.
+pub fn spawn<P: OnUpdateAsync>(fut: impl Future<Output = ()> + 'static) {
+    P::handler().push_task(Box::pin(fut));
+}
+
+fn raw_waker() -> RawWaker {
+    fn clone(_: *const ()) -> RawWaker {
+        raw_waker()
+    }
+    fn noop(_: *const ()) {}
+
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    RawWaker::new(core::ptr::null(), &VTABLE)
+}
+
+fn waker() -> Waker {
+    // Safety: the vtable's functions are all no-ops that don't touch the
+    // (null) data pointer.
+    unsafe { Waker::from_raw(raw_waker()) }
+}
+
+/// Frame-based async executor, layered on top of [`OnUpdate`].
+///
+/// Implement this instead of [`OnUpdate`] directly to get `async` task
+/// support: [`ffi_on_update_async`][Self::ffi_on_update_async] polls every pending task
+/// once per `GX2SwapScanBuffers`, removes the ones that completed, and then
+/// forwards to [`OnUpdate::ffi_on_update`].
+pub trait OnUpdateAsync: OnUpdate {
+    /// FFI callback for the frame hook, polling every pending task once and
+    /// then forwarding to [`OnUpdate::ffi_on_update`].
+    ///
+    /// **Do not overwrite** this method unless you need to and know what you
+    /// are doing!
+    extern "C" fn ffi_on_update_async() {
+        let waker = waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let handler = Self::handler();
+
+        // Take the list out from under the lock before polling: a task
+        // that calls `spawn` while being polled would otherwise try to
+        // re-lock this same (non-reentrant) mutex and deadlock.
+        let mut tasks = core::mem::take(
+            &mut *handler
+                .tasks
+                .lock()
+                .unwrap_or_else(|poison| poison.into_inner()),
+        );
+
+        tasks.retain_mut(|task| task.as_mut().poll(&mut cx) == Poll::Pending);
+
+        // Tasks spawned while polling landed in the handler's list in the
+        // meantime; keep them after the ones that were already pending.
+        let mut pending = handler
+            .tasks
+            .lock()
+            .unwrap_or_else(|poison| poison.into_inner());
+        tasks.append(&mut pending);
+        *pending = tasks;
+        drop(pending);
+
+        Self::ffi_on_update();
+    }
+}
+
+/// Future that resolves after `n` frames have been polled.
+pub struct FrameTimer {
+    remaining: u32,
+}
+
+impl FrameTimer {
+    /// Resolve after `frames` more frames.
+    pub fn new(frames: u32) -> Self {
+        Self { remaining: frames }
+    }
+}
+
+impl Future for FrameTimer {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        if self.remaining == 0 {
+            Poll::Ready(())
+        } else {
+            self.remaining -= 1;
+            Poll::Pending
+        }
+    }
+}
+
+/// Future that resolves once at least `duration` of wall-clock time has
+/// passed, for timing that shouldn't drift with frame rate.
+pub struct Delay {
+    until: wut::time::Instant,
+}
+
+impl Delay {
+    /// Resolve once `duration` has elapsed.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            until: wut::time::Instant::now() + duration,
+        }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        if wut::time::Instant::now() >= self.until {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}